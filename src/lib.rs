@@ -3,83 +3,323 @@
 
 //! Simple vector library
 
-use std::ops::{ Add, Mul, Neg, Sub };
+extern crate num;
 
-/// 2D Vector; A Vector with 2 components
-#[derive(Debug, Copy, Clone)]
-pub struct Vector2<T: Copy> {
-    /// Element at index 0
-    pub e0: T,
-    /// Element at index 1
-    pub e1: T,
-}
+use num::{ Float, One, Zero };
+use std::ops::{ Add, AddAssign, Div, Index, IndexMut, Mul, MulAssign, Neg, Rem, Sub, SubAssign };
 
-impl<T> Vector2<T>  where T: Copy {
+/// Generates a `VectorN<T>` struct with the named components plus the
+/// constructors, the `get` accessor, the floating-point geometry methods
+/// and the arithmetic, indexing and compound assignment operator impls
+/// shared by every vector type in this crate.
+macro_rules! make_vector {
+    ($Name:ident, $n:expr, ($first_idx:expr, $first_field:ident), $(($idx:expr, $field:ident)),*) => {
+        #[doc = concat!(stringify!($n), "D Vector; A Vector with ", stringify!($n), " components")]
+        #[repr(C)]
+        #[derive(Debug, Copy, Clone)]
+        pub struct $Name<T: Copy> {
+            #[doc = concat!("Element at index ", stringify!($first_idx))]
+            pub $first_field: T,
+            $(
+                #[doc = concat!("Element at index ", stringify!($idx))]
+                pub $field: T,
+            )*
+        }
 
-    /// Create new Vector2 with two entries of type T
-    #[inline(always)]
-    pub fn new(e0: T, e1: T) -> Vector2<T> {
-        Vector2::<T> { e0: e0, e1: e1 }
-    }
-    
-    /// Get entry by index
-    #[inline(always)]
-    pub fn get(self, index: usize) -> T {
-        match index {
-            0 => self.e0,
-            1 => self.e1,
-            _ => panic!("index out of bounds: the len is 2 but the index is {}", index)
+        impl<T> $Name<T> where T: Copy {
+
+            #[doc = concat!("Create new ", stringify!($Name), " with ", stringify!($n), " entries of type T")]
+            #[inline(always)]
+            pub fn new($first_field: T, $($field: T),*) -> $Name<T> {
+                $Name::<T> { $first_field: $first_field, $($field: $field),* }
+            }
+
+            /// Get entry by index
+            #[inline(always)]
+            pub fn get(self, index: usize) -> T {
+                match index {
+                    $first_idx => self.$first_field,
+                    $($idx => self.$field,)*
+                    _ => panic!("index out of bounds: the len is {} but the index is {}", $n, index)
+                }
+            }
+
+            /// Create new vector with `val` copied into every component
+            #[inline(always)]
+            pub fn broadcast(val: T) -> $Name<T> {
+                $Name::<T> { $first_field: val, $($field: val),* }
+            }
+
+            /// Build a new vector by calling `f` with each component index
+            #[inline(always)]
+            pub fn from_fn<F: FnMut(usize) -> T>(mut f: F) -> $Name<T> {
+                $Name::<T> { $first_field: f($first_idx), $($field: f($idx)),* }
+            }
         }
-    }
-}
 
-/// Adds other to self
-impl<T> Add<Vector2<T>> for Vector2<T> where T: Copy + Add<T, Output = T> {
-    type Output = Vector2<T>;
+        impl<T> $Name<T> where T: Copy + Zero {
 
-    #[inline(always)]
-    fn add (self, other: Vector2<T>) -> Vector2<T> {
-        Vector2::<T> { e0: self.e0 + other.e0, e1: self.e1 + other.e1 }
-    }
-}
+            /// Create new vector with every component set to zero
+            #[inline(always)]
+            pub fn zero() -> $Name<T> {
+                $Name::<T> { $first_field: T::zero(), $($field: T::zero()),* }
+            }
+        }
 
-/// Compute the DOT PRODUCT
-impl<T> Mul<Vector2<T>> for Vector2<T> where T: Copy + Mul<T, Output = T> + Add<T, Output = T> {
-    type Output = T;
+        impl<T> $Name<T> where T: Copy + One {
 
-    #[inline(always)]
-    fn mul (self, other: Vector2<T>) -> T {
-        self.e0 * other.e0 + self.e1 * other.e1
+            /// Create new vector with every component set to one
+            #[inline(always)]
+            pub fn one() -> $Name<T> {
+                $Name::<T> { $first_field: T::one(), $($field: T::one()),* }
+            }
+        }
+
+        impl<T> $Name<T> where T: Copy + Zero + One + AddAssign<T> {
+
+            /// Create new vector with components set to 0, 1, 2, ...
+            #[inline(always)]
+            pub fn iota() -> $Name<T> {
+                let mut next = T::zero();
+                let $first_field = next;
+                $(
+                    next += T::one();
+                    let $field = next;
+                )*
+                $Name::<T> { $first_field: $first_field, $($field: $field),* }
+            }
+        }
+
+        impl<T> $Name<T> where T: Float {
+
+            /// Returns the squared magnitude; the dot product of the vector with itself
+            #[inline(always)]
+            pub fn magnitude2(self) -> T {
+                self.$first_field * self.$first_field $(+ self.$field * self.$field)*
+            }
+
+            /// Returns the magnitude (length) of the vector
+            #[inline(always)]
+            pub fn magnitude(self) -> T {
+                self.magnitude2().sqrt()
+            }
+
+            /// Returns the vector scaled to a magnitude of one. The zero vector
+            /// has no direction, so normalizing it yields NaN components rather
+            /// than panicking
+            #[inline(always)]
+            pub fn normalize(self) -> $Name<T> {
+                self / self.magnitude()
+            }
+
+            /// Returns the distance between self and other
+            #[inline(always)]
+            pub fn distance(self, other: $Name<T>) -> T {
+                (self - other).magnitude()
+            }
+
+            /// Returns the angle between self and other, in radians. The cosine
+            /// argument is clamped to [-1, 1] since floating-point rounding can
+            /// push it a hair outside that domain even for non-degenerate inputs
+            #[inline(always)]
+            pub fn angle(self, other: $Name<T>) -> T {
+                let cos = self * other / (self.magnitude() * other.magnitude());
+                cos.max(-T::one()).min(T::one()).acos()
+            }
+        }
+
+        /// Adds other to self
+        impl<T> Add<$Name<T>> for $Name<T> where T: Copy + Add<T, Output = T> {
+            type Output = $Name<T>;
+
+            #[inline(always)]
+            fn add (self, other: $Name<T>) -> $Name<T> {
+                $Name::<T> { $first_field: self.$first_field + other.$first_field, $($field: self.$field + other.$field),* }
+            }
+        }
+
+        /// Compute the DOT PRODUCT
+        impl<T> Mul<$Name<T>> for $Name<T> where T: Copy + Mul<T, Output = T> + Add<T, Output = T> {
+            type Output = T;
+
+            #[inline(always)]
+            fn mul (self, other: $Name<T>) -> T {
+                self.$first_field * other.$first_field $(+ self.$field * other.$field)*
+            }
+        }
+
+        /// Preform an element-wise multiplication
+        impl<T> Mul<T> for $Name<T> where T: Copy + Mul<T, Output = T> {
+            type Output = $Name<T>;
+
+            #[inline(always)]
+            fn mul (self, scalar: T) -> $Name<T> {
+                $Name::<T> { $first_field: self.$first_field * scalar, $($field: self.$field * scalar),* }
+            }
+        }
+
+        impl<T> $Name<T> where T: Copy + Mul<T, Output = T> {
+
+            /// Multiply each component by scalar; named alternative to the `*` operator
+            #[inline(always)]
+            pub fn mul_scalar(self, scalar: T) -> $Name<T> {
+                self * scalar
+            }
+
+            /// Multiply self and other component-wise
+            #[inline(always)]
+            pub fn mul_element_wise(self, other: $Name<T>) -> $Name<T> {
+                $Name::<T> { $first_field: self.$first_field * other.$first_field, $($field: self.$field * other.$field),* }
+            }
+        }
+
+        /// Preform an element-wise division by a scalar
+        impl<T> Div<T> for $Name<T> where T: Copy + Div<T, Output = T> {
+            type Output = $Name<T>;
+
+            #[inline(always)]
+            fn div (self, scalar: T) -> $Name<T> {
+                $Name::<T> { $first_field: self.$first_field / scalar, $($field: self.$field / scalar),* }
+            }
+        }
+
+        /// Preform an element-wise division
+        impl<T> Div<$Name<T>> for $Name<T> where T: Copy + Div<T, Output = T> {
+            type Output = $Name<T>;
+
+            #[inline(always)]
+            fn div (self, other: $Name<T>) -> $Name<T> {
+                $Name::<T> { $first_field: self.$first_field / other.$first_field, $($field: self.$field / other.$field),* }
+            }
+        }
+
+        impl<T> $Name<T> where T: Copy + Div<T, Output = T> {
+
+            /// Divide each component by scalar; named alternative to the `/` operator
+            #[inline(always)]
+            pub fn div_scalar(self, scalar: T) -> $Name<T> {
+                self / scalar
+            }
+        }
+
+        /// Preform an element-wise remainder by a scalar
+        impl<T> Rem<T> for $Name<T> where T: Copy + Rem<T, Output = T> {
+            type Output = $Name<T>;
+
+            #[inline(always)]
+            fn rem (self, scalar: T) -> $Name<T> {
+                $Name::<T> { $first_field: self.$first_field % scalar, $($field: self.$field % scalar),* }
+            }
+        }
+
+        /// Subtracts other from self
+        impl<T> Sub<$Name<T>> for $Name<T> where T: Copy + Sub<T, Output = T> {
+            type Output = $Name<T>;
+
+            #[inline(always)]
+            fn sub (self, other: $Name<T>) -> $Name<T> {
+                $Name::<T> { $first_field: self.$first_field - other.$first_field, $($field: self.$field - other.$field),* }
+            }
+        }
+
+        /// Negate each entry in the Vector
+        impl<T> Neg for $Name<T> where T: Copy + Neg<Output = T> {
+            type Output = $Name<T>;
+
+            #[inline(always)]
+            fn neg(self) -> $Name<T> {
+                $Name::<T> { $first_field: -self.$first_field, $($field: -self.$field),* }
+            }
+        }
+
+        /// Get entry by index, panicking out of bounds the same as `get`
+        impl<T> Index<usize> for $Name<T> where T: Copy {
+            type Output = T;
+
+            #[inline(always)]
+            fn index(&self, index: usize) -> &T {
+                match index {
+                    $first_idx => &self.$first_field,
+                    $($idx => &self.$field,)*
+                    _ => panic!("index out of bounds: the len is {} but the index is {}", $n, index)
+                }
+            }
+        }
+
+        /// Get a mutable reference to an entry by index, panicking out of bounds the same as `get`
+        impl<T> IndexMut<usize> for $Name<T> where T: Copy {
+            #[inline(always)]
+            fn index_mut(&mut self, index: usize) -> &mut T {
+                match index {
+                    $first_idx => &mut self.$first_field,
+                    $($idx => &mut self.$field,)*
+                    _ => panic!("index out of bounds: the len is {} but the index is {}", $n, index)
+                }
+            }
+        }
+
+        /// Adds other to self in place
+        impl<T> AddAssign<$Name<T>> for $Name<T> where T: Copy + AddAssign<T> {
+            #[inline(always)]
+            fn add_assign(&mut self, other: $Name<T>) {
+                self.$first_field += other.$first_field;
+                $(self.$field += other.$field;)*
+            }
+        }
+
+        /// Subtracts other from self in place
+        impl<T> SubAssign<$Name<T>> for $Name<T> where T: Copy + SubAssign<T> {
+            #[inline(always)]
+            fn sub_assign(&mut self, other: $Name<T>) {
+                self.$first_field -= other.$first_field;
+                $(self.$field -= other.$field;)*
+            }
+        }
+
+        /// Preform an element-wise multiplication in place
+        impl<T> MulAssign<T> for $Name<T> where T: Copy + MulAssign<T> {
+            #[inline(always)]
+            fn mul_assign(&mut self, scalar: T) {
+                self.$first_field *= scalar;
+                $(self.$field *= scalar;)*
+            }
+        }
     }
 }
 
-/// Preform an element-wise multiplication
-impl<T> Mul<T> for Vector2<T> where T: Copy + Mul<T, Output = T> {
-    type Output = Vector2<T>;
+make_vector!(Vector2, 2, (0, e0), (1, e1));
+make_vector!(Vector3, 3, (0, e0), (1, e1), (2, e2));
+make_vector!(Vector4, 4, (0, e0), (1, e1), (2, e2), (3, e3));
 
+impl<T> Vector3<T> where T: Copy + Mul<T, Output = T> + Sub<T, Output = T> {
+
+    /// Compute the CROSS PRODUCT
     #[inline(always)]
-    fn mul (self, scalar: T) -> Vector2<T> {
-        Vector2::<T> { e0: self.e0 * scalar, e1: self.e1 * scalar }
+    pub fn cross(self, other: Vector3<T>) -> Vector3<T> {
+        Vector3::<T> {
+            e0: self.e1 * other.e2 - self.e2 * other.e1,
+            e1: self.e2 * other.e0 - self.e0 * other.e2,
+            e2: self.e0 * other.e1 - self.e1 * other.e0,
+        }
     }
 }
 
-/// Subtracts other from self
-impl<T> Sub<Vector2<T>> for Vector2<T> where T: Copy + Sub<T, Output = T> {
-    type Output = Vector2<T>;
+impl<T> Vector2<T> where T: Copy + Mul<T, Output = T> + Sub<T, Output = T> {
 
+    /// Compute the 2D analog of the cross product; the signed z component of
+    /// the 3D cross product of the two vectors embedded in the XY plane
     #[inline(always)]
-    fn sub (self, other: Vector2<T>) -> Vector2<T> {
-        Vector2::<T> { e0: self.e0 - other.e0, e1: self.e1 - other.e1 }
+    pub fn perp_dot(self, other: Vector2<T>) -> T {
+        self.e0 * other.e1 - self.e1 * other.e0
     }
 }
 
-/// Negate each entry in the Vector2
-impl<T> Neg for Vector2<T> where T: Copy + Neg<Output = T> {
-    type Output = Vector2<T>;
+impl<T> Vector2<T> where T: Copy + Neg<Output = T> {
 
+    /// Returns the vector rotated 90 degrees counter-clockwise
     #[inline(always)]
-    fn neg(self) -> Vector2<T> {
-        Vector2::<T>{ e0: -self.e0, e1: -self.e1 }
+    pub fn perp(self) -> Vector2<T> {
+        Vector2::<T> { e0: -self.e1, e1: self.e0 }
     }
 }
 
@@ -139,3 +379,290 @@ fn test_get_xy() {
     assert_eq!(v.get(1), 2);
     v.get(2);
 }
+
+#[test]
+fn test_vector3_add() {
+    let v1 = Vector3::<i32>::new(2, 6, 1);
+    let v2 = Vector3::<i32>::new(4, 8, 3);
+    let v3 = v1 + v2;
+
+    assert_eq!(v3.e0, 6);
+    assert_eq!(v3.e1, 14);
+    assert_eq!(v3.e2, 4);
+}
+
+#[test]
+fn test_vector3_dot() {
+    let v1 = Vector3::<i32>::new(2, 3, 4);
+    let v2 = Vector3::<i32>::new(4, 5, 6);
+    let s = v1 * v2;
+
+    assert_eq!(s, 47);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+fn test_vector3_get_out_of_bounds() {
+    let v = Vector3::<i32>::new(1, 2, 3);
+
+    v.get(3);
+}
+
+#[test]
+fn test_vector4_add() {
+    let v1 = Vector4::<i32>::new(2, 6, 1, 5);
+    let v2 = Vector4::<i32>::new(4, 8, 3, 2);
+    let v3 = v1 + v2;
+
+    assert_eq!(v3.e0, 6);
+    assert_eq!(v3.e1, 14);
+    assert_eq!(v3.e2, 4);
+    assert_eq!(v3.e3, 7);
+}
+
+#[test]
+fn test_vector4_dot() {
+    let v1 = Vector4::<i32>::new(1, 2, 3, 4);
+    let v2 = Vector4::<i32>::new(5, 6, 7, 8);
+    let s = v1 * v2;
+
+    assert_eq!(s, 70);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 4 but the index is 4")]
+fn test_vector4_get_out_of_bounds() {
+    let v = Vector4::<i32>::new(1, 2, 3, 4);
+
+    v.get(4);
+}
+
+#[test]
+fn test_vector2_broadcast() {
+    let v = Vector2::<i32>::broadcast(7);
+
+    assert_eq!(v.e0, 7);
+    assert_eq!(v.e1, 7);
+}
+
+#[test]
+fn test_vector2_zero() {
+    let v = Vector2::<i32>::zero();
+
+    assert_eq!(v.e0, 0);
+    assert_eq!(v.e1, 0);
+}
+
+#[test]
+fn test_vector2_one() {
+    let v = Vector2::<i32>::one();
+
+    assert_eq!(v.e0, 1);
+    assert_eq!(v.e1, 1);
+}
+
+#[test]
+fn test_vector3_iota() {
+    let v = Vector3::<i32>::iota();
+
+    assert_eq!(v.e0, 0);
+    assert_eq!(v.e1, 1);
+    assert_eq!(v.e2, 2);
+}
+
+#[test]
+fn test_vector3_from_fn() {
+    let v = Vector3::<i32>::from_fn(|i| (i * 2) as i32);
+
+    assert_eq!(v.e0, 0);
+    assert_eq!(v.e1, 2);
+    assert_eq!(v.e2, 4);
+}
+
+#[test]
+fn test_vector2_index() {
+    let v = Vector2::<i32>::new(3, 9);
+
+    assert_eq!(v[0], 3);
+    assert_eq!(v[1], 9);
+}
+
+#[test]
+fn test_vector2_index_mut() {
+    let mut v = Vector2::<i32>::new(3, 9);
+    v[0] = 10;
+
+    assert_eq!(v[0], 10);
+    assert_eq!(v[1], 9);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+fn test_vector2_index_out_of_bounds() {
+    let v = Vector2::<i32>::new(3, 9);
+
+    let _ = v[2];
+}
+
+#[test]
+fn test_vector2_add_assign() {
+    let mut v1 = Vector2::<i32>::new(2, 6);
+    let v2 = Vector2::<i32>::new(4, 8);
+    v1 += v2;
+
+    assert_eq!(v1.e0, 6);
+    assert_eq!(v1.e1, 14);
+}
+
+#[test]
+fn test_vector2_sub_assign() {
+    let mut v1 = Vector2::<i32>::new(7, 8);
+    let v2 = Vector2::<i32>::new(2, 9);
+    v1 -= v2;
+
+    assert_eq!(v1.e0, 5);
+    assert_eq!(v1.e1, -1);
+}
+
+#[test]
+fn test_vector2_mul_assign() {
+    let mut v = Vector2::<i32>::new(1, 2);
+    v *= 2;
+
+    assert_eq!(v.e0, 2);
+    assert_eq!(v.e1, 4);
+}
+
+#[test]
+fn test_vector2_magnitude2() {
+    let v = Vector2::<f64>::new(3.0, 4.0);
+
+    assert_eq!(v.magnitude2(), 25.0);
+}
+
+#[test]
+fn test_vector2_magnitude() {
+    let v = Vector2::<f64>::new(3.0, 4.0);
+
+    assert_eq!(v.magnitude(), 5.0);
+}
+
+#[test]
+fn test_vector2_normalize() {
+    let v = Vector2::<f64>::new(3.0, 4.0).normalize();
+
+    assert_eq!(v.e0, 0.6);
+    assert_eq!(v.e1, 0.8);
+}
+
+#[test]
+fn test_vector2_normalize_zero_is_nan() {
+    let v = Vector2::<f64>::zero().normalize();
+
+    assert!(v.e0.is_nan());
+    assert!(v.e1.is_nan());
+}
+
+#[test]
+fn test_vector2_distance() {
+    let v1 = Vector2::<f64>::new(0.0, 0.0);
+    let v2 = Vector2::<f64>::new(3.0, 4.0);
+
+    assert_eq!(v1.distance(v2), 5.0);
+}
+
+#[test]
+fn test_vector2_angle() {
+    let v1 = Vector2::<f64>::new(1.0, 0.0);
+    let v2 = Vector2::<f64>::new(0.0, 1.0);
+
+    assert!((v1.angle(v2) - std::f64::consts::FRAC_PI_2).abs() < 1e-10);
+}
+
+#[test]
+fn test_vector3_angle_nearly_parallel_is_not_nan() {
+    let v1 = Vector3::<f64>::new(18.241, 7.817, -28.162);
+    let v2 = v1 * (1.0 + 1e-9);
+
+    assert!(!v1.angle(v2).is_nan());
+    assert!(v1.angle(v2).abs() < 1e-4);
+}
+
+#[test]
+fn test_vector2_div() {
+    let v = Vector2::<f64>::new(4.0, 8.0) / 2.0;
+
+    assert_eq!(v.e0, 2.0);
+    assert_eq!(v.e1, 4.0);
+}
+
+#[test]
+fn test_vector3_cross() {
+    let v1 = Vector3::<i32>::new(1, 0, 0);
+    let v2 = Vector3::<i32>::new(0, 1, 0);
+    let v3 = v1.cross(v2);
+
+    assert_eq!(v3.e0, 0);
+    assert_eq!(v3.e1, 0);
+    assert_eq!(v3.e2, 1);
+}
+
+#[test]
+fn test_vector2_perp_dot() {
+    let v1 = Vector2::<i32>::new(1, 0);
+    let v2 = Vector2::<i32>::new(0, 1);
+
+    assert_eq!(v1.perp_dot(v2), 1);
+}
+
+#[test]
+fn test_vector2_perp() {
+    let v = Vector2::<i32>::new(1, 2).perp();
+
+    assert_eq!(v.e0, -2);
+    assert_eq!(v.e1, 1);
+}
+
+#[test]
+fn test_vector2_mul_scalar() {
+    let v = Vector2::<i32>::new(1, 2).mul_scalar(3);
+
+    assert_eq!(v.e0, 3);
+    assert_eq!(v.e1, 6);
+}
+
+#[test]
+fn test_vector2_mul_element_wise() {
+    let v1 = Vector2::<i32>::new(2, 3);
+    let v2 = Vector2::<i32>::new(4, 5);
+    let v3 = v1.mul_element_wise(v2);
+
+    assert_eq!(v3.e0, 8);
+    assert_eq!(v3.e1, 15);
+}
+
+#[test]
+fn test_vector2_div_scalar() {
+    let v = Vector2::<i32>::new(4, 8).div_scalar(2);
+
+    assert_eq!(v.e0, 2);
+    assert_eq!(v.e1, 4);
+}
+
+#[test]
+fn test_vector2_div_element_wise() {
+    let v1 = Vector2::<i32>::new(8, 9);
+    let v2 = Vector2::<i32>::new(4, 3);
+    let v3 = v1 / v2;
+
+    assert_eq!(v3.e0, 2);
+    assert_eq!(v3.e1, 3);
+}
+
+#[test]
+fn test_vector2_rem() {
+    let v = Vector2::<i32>::new(7, 9) % 4;
+
+    assert_eq!(v.e0, 3);
+    assert_eq!(v.e1, 1);
+}